@@ -1,33 +1,44 @@
 use crate::{core::ics24_host::identifier::ClientId, prelude::*};
 use core::fmt::{Display, Error as FmtError, Formatter};
 use serde_derive::{Deserialize, Serialize};
-use strum::IntoEnumIterator;
+use std::sync::{OnceLock, RwLock};
 
 use super::error::Error;
 
+/// One `(code, prefix)` pairing known to this crate, either built in or
+/// registered at runtime via [`ClientType::register`].
+#[derive(Clone, Copy)]
+struct ClientTypeEntry {
+    code: u64,
+    prefix: &'static str,
+}
+
+/// Client types registered at runtime, for consensus types this crate
+/// doesn't know about at compile time (e.g. a future chain's light client).
+/// Keyed separately from the built-in entries so registration can never
+/// shadow or be shadowed by them.
+static REGISTRY: OnceLock<RwLock<Vec<ClientTypeEntry>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Vec<ClientTypeEntry>> {
+    REGISTRY.get_or_init(|| RwLock::new(Vec::new()))
+}
+
 /// Type of the client, depending on the specific consensus algorithm.
-#[derive(
-    Copy,
-    Clone,
-    Debug,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Hash,
-    Serialize,
-    Deserialize,
-    strum::EnumIter,
-)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ClientType {
-    Tendermint = 1,
-    Eth = 2,
-    Ckb = 3,
-    Axon = 4,
-    Ckb4Ibc = 5,
+    Tendermint,
+    Eth,
+    Ckb,
+    Axon,
+    Ckb4Ibc,
+
+    /// A client type registered at runtime via [`ClientType::register`],
+    /// identified by its registered numeric code rather than a variant
+    /// known at compile time.
+    Other(u64),
 
     #[cfg(any(test, feature = "mocks"))]
-    Mock = 255,
+    Mock,
 }
 
 impl ClientType {
@@ -40,6 +51,56 @@ impl ClientType {
     #[cfg_attr(not(test), allow(dead_code))]
     const MOCK_STR: &'static str = "9999-mock";
 
+    /// The `(code, prefix)` pairs built into this crate, in the order they
+    /// should be tried when matching a `ClientId` prefix.
+    const BUILTIN_ENTRIES: &'static [(u64, &'static str)] = &[
+        (1, Self::TENDERMINT_STR),
+        (2, Self::ETH_STR),
+        (3, Self::CKB_STR),
+        (4, Self::AXON_STR),
+        (5, Self::CKB4IBC_STR),
+    ];
+
+    /// Registers a new consensus client type identified by `code` and
+    /// `prefix`, so [`TryFrom<u64>`], [`FromStr`](core::str::FromStr) and
+    /// [`TryFrom<ClientId>`] all recognize it without a new match arm (and
+    /// without a new release of this crate).
+    ///
+    /// Fails if `code` is already taken (built in or previously
+    /// registered), or if `prefix` would be ambiguous with an existing
+    /// prefix: prefix lookups scan for the first prefix match, so one
+    /// prefix being a prefix of another makes the match depend on
+    /// registration order.
+    pub fn register(code: u64, prefix: &'static str) -> Result<(), Error> {
+        let code_taken = Self::BUILTIN_ENTRIES.iter().any(|(c, _)| *c == code);
+        #[cfg(any(test, feature = "mocks"))]
+        let code_taken = code_taken || code == 9999;
+        if code_taken {
+            return Err(Error::client_type_already_registered(code.to_string()));
+        }
+
+        let mut entries = registry().write().unwrap();
+        if entries.iter().any(|e| e.code == code) {
+            return Err(Error::client_type_already_registered(code.to_string()));
+        }
+
+        let ambiguous = Self::BUILTIN_ENTRIES
+            .iter()
+            .map(|(_, known)| *known)
+            .chain(entries.iter().map(|e| e.prefix))
+            .any(|known| known.starts_with(prefix) || prefix.starts_with(known));
+        #[cfg(any(test, feature = "mocks"))]
+        let ambiguous = ambiguous
+            || Self::MOCK_STR.starts_with(prefix)
+            || prefix.starts_with(Self::MOCK_STR);
+        if ambiguous {
+            return Err(Error::ambiguous_client_type_prefix(prefix.to_string()));
+        }
+
+        entries.push(ClientTypeEntry { code, prefix });
+        Ok(())
+    }
+
     /// Yields the identifier of this client type as a string
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -48,6 +109,13 @@ impl ClientType {
             Self::Ckb => Self::CKB_STR,
             Self::Axon => Self::AXON_STR,
             Self::Ckb4Ibc => Self::CKB4IBC_STR,
+            Self::Other(code) => registry()
+                .read()
+                .unwrap()
+                .iter()
+                .find(|e| e.code == *code)
+                .map(|e| e.prefix)
+                .unwrap_or("unknown-client-type"),
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock => Self::MOCK_STR,
@@ -67,20 +135,43 @@ impl TryFrom<u64> for ClientType {
 
             #[cfg(any(test, feature = "mocks"))]
             9999 => Ok(Self::Mock),
+
+            _ if registry().read().unwrap().iter().any(|e| e.code == value) => {
+                Ok(Self::Other(value))
+            }
             _ => Err(Error::unknown_client_type(value.to_string())),
         }
     }
 }
 
-impl From<ClientId> for ClientType {
-    fn from(client_id: ClientId) -> Self {
-        let mut client_type = ClientType::Mock;
-        for value in ClientType::iter() {
-            if client_id.as_str().starts_with(value.as_str()) {
-                client_type = value;
+impl TryFrom<ClientId> for ClientType {
+    type Error = Error;
+
+    /// Resolves the client type whose prefix `client_id` starts with. An
+    /// unrecognized prefix is an explicit error, not a silent fallback to
+    /// [`ClientType::Mock`].
+    fn try_from(client_id: ClientId) -> Result<Self, Self::Error> {
+        let id = client_id.as_str();
+
+        for (code, prefix) in ClientType::BUILTIN_ENTRIES.iter().copied() {
+            if id.starts_with(prefix) {
+                return ClientType::try_from(code);
+            }
+        }
+
+        #[cfg(any(test, feature = "mocks"))]
+        if id.starts_with(ClientType::MOCK_STR) {
+            return Ok(ClientType::Mock);
+        }
+
+        let entries = registry().read().unwrap();
+        for entry in entries.iter() {
+            if id.starts_with(entry.prefix) {
+                return Ok(ClientType::Other(entry.code));
             }
         }
-        client_type
+
+        Err(Error::unknown_client_type(id.to_string()))
     }
 }
 
@@ -104,7 +195,13 @@ impl core::str::FromStr for ClientType {
             #[cfg(any(test, feature = "mocks"))]
             Self::MOCK_STR => Ok(Self::Mock),
 
-            _ => Err(Error::unknown_client_type(s.to_string())),
+            _ => registry()
+                .read()
+                .unwrap()
+                .iter()
+                .find(|e| e.prefix == s)
+                .map(|e| Self::Other(e.code))
+                .ok_or_else(|| Error::unknown_client_type(s.to_string())),
         }
     }
 }
@@ -116,6 +213,7 @@ mod tests {
 
     use super::ClientType;
     use crate::core::ics02_client::error::{Error, ErrorDetail};
+    use crate::core::ics24_host::identifier::ClientId;
 
     #[test]
     fn parse_tendermint_client_type() {
@@ -167,4 +265,59 @@ mod tests {
         let client_type_from_str = ClientType::from_str(type_string).unwrap();
         assert_eq!(client_type_from_str, client_type);
     }
+
+    #[test]
+    fn register_and_resolve_new_client_type() {
+        ClientType::register(1000, "99-future-chain").unwrap();
+
+        assert_eq!(
+            ClientType::try_from(1000u64).unwrap(),
+            ClientType::Other(1000)
+        );
+        assert_eq!(
+            ClientType::from_str("99-future-chain").unwrap(),
+            ClientType::Other(1000)
+        );
+
+        let client_id = ClientId::from_str("99-future-chain-0").unwrap();
+        assert_eq!(
+            ClientType::try_from(client_id).unwrap(),
+            ClientType::Other(1000)
+        );
+    }
+
+    #[test]
+    fn register_rejects_ambiguous_prefix() {
+        ClientType::register(1001, "99-ambiguous").unwrap();
+
+        let result = ClientType::register(1002, "99-ambiguous-extended");
+        match result {
+            Err(Error(ErrorDetail::AmbiguousClientTypePrefix(e), _)) => {
+                assert_eq!(&e.prefix, "99-ambiguous-extended")
+            }
+            _ => panic!("Expected register to fail with AmbiguousClientTypePrefix, instead got"),
+        }
+    }
+
+    #[test]
+    fn register_rejects_taken_code() {
+        let result = ClientType::register(1, "99-shadow-tendermint");
+        match result {
+            Err(Error(ErrorDetail::ClientTypeAlreadyRegistered(_), _)) => (),
+            _ => panic!(
+                "Expected register to fail with ClientTypeAlreadyRegistered, instead got"
+            ),
+        }
+    }
+
+    #[test]
+    fn unrecognized_client_id_is_an_explicit_error() {
+        let client_id = ClientId::from_str("06-unknown-0").unwrap();
+        let result = ClientType::try_from(client_id);
+
+        match result {
+            Err(Error(ErrorDetail::UnknownClientType(_), _)) => (),
+            _ => panic!("Expected an explicit UnknownClientType error, instead got"),
+        }
+    }
 }
@@ -0,0 +1,27 @@
+use flex_error::define_error;
+
+define_error! {
+    Error {
+        UnknownClientType
+            { client_type: String }
+            | e | { format_args!("unknown client type: {0}", e.client_type) },
+
+        ClientTypeAlreadyRegistered
+            { code: String }
+            | e | {
+                format_args!(
+                    "client type code {0} is already registered",
+                    e.code
+                )
+            },
+
+        AmbiguousClientTypePrefix
+            { prefix: String }
+            | e | {
+                format_args!(
+                    "client type prefix '{0}' is ambiguous with an already-registered prefix",
+                    e.prefix
+                )
+            },
+    }
+}
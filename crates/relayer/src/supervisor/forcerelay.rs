@@ -1,5 +1,10 @@
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::events::IbcEvent;
-use std::{thread, time::Duration};
+use ibc_relayer_types::Height;
+use ibc_proto::google::protobuf::Any;
+use rand::Rng;
+use serde::Deserialize;
+use std::{fs, path::PathBuf, thread, time::Duration};
 use tracing::{error, info, warn};
 
 use crate::chain::handle::ChainHandle;
@@ -9,26 +14,666 @@ use crate::client_state::IdentifiedAnyClientState;
 use crate::config::ChainConfig;
 use crate::error::ErrorDetail::LightClientVerification;
 use crate::event::monitor::EventBatch;
-use tendermint_light_client::errors::ErrorDetail::MissingLastBlockId;
+use tendermint_light_client::errors::ErrorDetail::{InvalidSignature, MissingLastBlockId};
 
 const MAX_HEADERS_IN_BATCH: u64 = 32;
+const RETRY_BASE_SLEEP_INTERVAL: Duration = Duration::from_millis(500);
 const MAX_RETRY_SLEEP_INTERVAL: Duration = Duration::from_secs(12);
 const MAX_RETRY_NUMBER: u8 = 5;
+
+/// Directory checkpoints are persisted under, overridable for tests/ops via
+/// `FORCERELAY_CHECKPOINT_DIR`.
+const CHECKPOINT_DIR_ENV: &str = "FORCERELAY_CHECKPOINT_DIR";
+const DEFAULT_CHECKPOINT_DIR: &str = ".forcerelay/checkpoints";
+
+/// Number of slots in an epoch, per the Altair beacon chain spec.
+const SLOTS_PER_EPOCH: u64 = 32;
+/// Number of epochs a single sync committee is valid for.
+const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+/// Number of slots a single sync committee is valid for (8192).
+const SLOTS_PER_SYNC_COMMITTEE_PERIOD: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+
+/// The sync-committee period that `slot` falls into, per the Altair spec.
+fn sync_committee_period(slot: u64) -> u64 {
+    slot / SLOTS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// The last height a chase window starting at `start_height` may cover
+/// without crossing out of `start_height`'s sync-committee period, capped at
+/// `target_height`. Pulled out of `EthRoute::window_end` so the boundary
+/// math can be unit tested without a `ChainHandle`.
+fn sync_committee_window_end(start_height: u64, target_height: u64) -> u64 {
+    let period = sync_committee_period(start_height);
+    let period_end = (period + 1) * SLOTS_PER_SYNC_COMMITTEE_PERIOD - 1;
+    std::cmp::min(period_end, target_height)
+}
+
+/// A beacon chain block header, as referenced by a [`LightClientUpdate`].
+#[derive(Clone, Debug)]
+pub struct BeaconBlockHeader {
+    pub slot: u64,
+    pub state_root: [u8; 32],
+}
+
+/// An Altair sync committee: the set of validators attesting to headers for
+/// one [`SLOTS_PER_SYNC_COMMITTEE_PERIOD`]-slot period.
+#[derive(Clone, Debug)]
+pub struct SyncCommittee {
+    pub pubkeys: Vec<[u8; 48]>,
+    pub aggregate_pubkey: [u8; 48],
+}
+
+/// The BLS aggregate signature over an attested header, together with the
+/// bitfield of participating sync committee members.
+#[derive(Clone, Debug)]
+pub struct SyncAggregate {
+    pub sync_committee_bits: Vec<u8>,
+    pub sync_committee_signature: [u8; 96],
+}
+
+/// An Altair `LightClientUpdate`, as defined by the beacon chain light client
+/// sync protocol. Relaying one of these across a sync-committee period
+/// boundary is what lets the on-chain ETH light client keep verifying
+/// `SyncAggregate`s signed by the new committee.
+#[derive(Clone, Debug)]
+pub struct LightClientUpdate {
+    pub attested_header: BeaconBlockHeader,
+    pub next_sync_committee: SyncCommittee,
+    pub next_sync_committee_branch: Vec<[u8; 32]>,
+    pub finalized_header: BeaconBlockHeader,
+    pub finality_branch: Vec<[u8; 32]>,
+    pub sync_aggregate: SyncAggregate,
+    pub signature_slot: u64,
+}
+
+fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn encode_varint_field(field_number: u32, value: u64, buf: &mut Vec<u8>) {
+    encode_varint(((field_number as u64) << 3) | 0, buf);
+    encode_varint(value, buf);
+}
+
+fn encode_bytes_field(field_number: u32, bytes: &[u8], buf: &mut Vec<u8>) {
+    encode_varint(((field_number as u64) << 3) | 2, buf);
+    encode_varint(bytes.len() as u64, buf);
+    buf.extend_from_slice(bytes);
+}
+
+impl BeaconBlockHeader {
+    fn encode_to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_varint_field(1, self.slot, &mut buf);
+        encode_bytes_field(2, &self.state_root, &mut buf);
+        buf
+    }
+}
+
+impl SyncCommittee {
+    fn encode_to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for pubkey in &self.pubkeys {
+            encode_bytes_field(1, pubkey, &mut buf);
+        }
+        encode_bytes_field(2, &self.aggregate_pubkey, &mut buf);
+        buf
+    }
+}
+
+impl SyncAggregate {
+    fn encode_to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_bytes_field(1, &self.sync_committee_bits, &mut buf);
+        encode_bytes_field(2, &self.sync_committee_signature, &mut buf);
+        buf
+    }
+}
+
+impl LightClientUpdate {
+    fn encode_to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_bytes_field(1, &self.attested_header.encode_to_vec(), &mut buf);
+        encode_bytes_field(2, &self.next_sync_committee.encode_to_vec(), &mut buf);
+        for branch in &self.next_sync_committee_branch {
+            encode_bytes_field(3, branch, &mut buf);
+        }
+        encode_bytes_field(4, &self.finalized_header.encode_to_vec(), &mut buf);
+        for branch in &self.finality_branch {
+            encode_bytes_field(5, branch, &mut buf);
+        }
+        encode_bytes_field(6, &self.sync_aggregate.encode_to_vec(), &mut buf);
+        encode_varint_field(7, self.signature_slot, &mut buf);
+        buf
+    }
+}
+
+/// The `type_url` ETH `LightClientUpdate`s are tagged with when packaged as
+/// `Any`, matching the `/<package>.<Message>` convention `ibc_proto` uses
+/// throughout.
+const LIGHT_CLIENT_UPDATE_TYPE_URL: &str = "/forcerelay.eth.v1.LightClientUpdate";
+
+impl From<LightClientUpdate> for Any {
+    fn from(update: LightClientUpdate) -> Self {
+        Any {
+            type_url: LIGHT_CLIENT_UPDATE_TYPE_URL.to_string(),
+            value: update.encode_to_vec(),
+        }
+    }
+}
+
+/// Overrides the beacon-chain REST endpoint light client updates are
+/// fetched from, for tests/ops. Falls back to the default local beacon node
+/// port when unset, mirroring `CHECKPOINT_DIR_ENV` above.
+const BEACON_API_URL_ENV: &str = "FORCERELAY_BEACON_API_URL";
+const DEFAULT_BEACON_API_URL: &str = "http://127.0.0.1:5052";
+
+fn decode_hex_bytes(hex: &str) -> Result<Vec<u8>, crate::error::Error> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| crate::error::Error::beacon_api_decode(format!("invalid hex: {hex}")))
+        })
+        .collect()
+}
+
+fn decode_hex_array<const N: usize>(hex: &str) -> Result<[u8; N], crate::error::Error> {
+    let bytes = decode_hex_bytes(hex)?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| crate::error::Error::beacon_api_decode(format!("expected {N} bytes, got {len}")))
+}
+
+/// The beacon-API light client update response shape, as served by
+/// `GET /eth/v1/beacon/light_client/updates`. Byte fields arrive as
+/// `0x`-prefixed hex strings and integers as decimal strings, per the
+/// beacon-API JSON convention.
+#[derive(Deserialize)]
+struct BeaconApiUpdateEnvelope {
+    data: BeaconApiUpdate,
+}
+
+#[derive(Deserialize)]
+struct BeaconApiUpdate {
+    attested_header: BeaconApiHeaderEnvelope,
+    next_sync_committee: BeaconApiSyncCommittee,
+    next_sync_committee_branch: Vec<String>,
+    finalized_header: BeaconApiHeaderEnvelope,
+    finality_branch: Vec<String>,
+    sync_aggregate: BeaconApiSyncAggregate,
+    signature_slot: String,
+}
+
+#[derive(Deserialize)]
+struct BeaconApiHeaderEnvelope {
+    beacon: BeaconApiHeader,
+}
+
+#[derive(Deserialize)]
+struct BeaconApiHeader {
+    slot: String,
+    state_root: String,
+}
+
+#[derive(Deserialize)]
+struct BeaconApiSyncCommittee {
+    pubkeys: Vec<String>,
+    aggregate_pubkey: String,
+}
+
+#[derive(Deserialize)]
+struct BeaconApiSyncAggregate {
+    sync_committee_bits: String,
+    sync_committee_signature: String,
+}
+
+impl TryFrom<BeaconApiHeaderEnvelope> for BeaconBlockHeader {
+    type Error = crate::error::Error;
+
+    fn try_from(envelope: BeaconApiHeaderEnvelope) -> Result<Self, Self::Error> {
+        Ok(BeaconBlockHeader {
+            slot: envelope
+                .beacon
+                .slot
+                .parse()
+                .map_err(|e: std::num::ParseIntError| crate::error::Error::beacon_api_decode(e.to_string()))?,
+            state_root: decode_hex_array(&envelope.beacon.state_root)?,
+        })
+    }
+}
+
+impl TryFrom<BeaconApiUpdate> for LightClientUpdate {
+    type Error = crate::error::Error;
+
+    fn try_from(update: BeaconApiUpdate) -> Result<Self, Self::Error> {
+        Ok(LightClientUpdate {
+            attested_header: update.attested_header.try_into()?,
+            next_sync_committee: SyncCommittee {
+                pubkeys: update
+                    .next_sync_committee
+                    .pubkeys
+                    .iter()
+                    .map(|pubkey| decode_hex_array(pubkey))
+                    .collect::<Result<Vec<_>, _>>()?,
+                aggregate_pubkey: decode_hex_array(&update.next_sync_committee.aggregate_pubkey)?,
+            },
+            next_sync_committee_branch: update
+                .next_sync_committee_branch
+                .iter()
+                .map(|branch| decode_hex_array(branch))
+                .collect::<Result<Vec<_>, _>>()?,
+            finalized_header: update.finalized_header.try_into()?,
+            finality_branch: update
+                .finality_branch
+                .iter()
+                .map(|branch| decode_hex_array(branch))
+                .collect::<Result<Vec<_>, _>>()?,
+            sync_aggregate: SyncAggregate {
+                sync_committee_bits: decode_hex_bytes(&update.sync_aggregate.sync_committee_bits)?,
+                sync_committee_signature: decode_hex_array(
+                    &update.sync_aggregate.sync_committee_signature,
+                )?,
+            },
+            signature_slot: update
+                .signature_slot
+                .parse()
+                .map_err(|e: std::num::ParseIntError| crate::error::Error::beacon_api_decode(e.to_string()))?,
+        })
+    }
+}
+
+/// Fetches the sync-committee `LightClientUpdate` for `period` from the
+/// beacon-API endpoint configured via `BEACON_API_URL_ENV`.
+fn fetch_beacon_light_client_update(period: u64) -> Result<LightClientUpdate, crate::error::Error> {
+    let base_url =
+        std::env::var(BEACON_API_URL_ENV).unwrap_or_else(|_| DEFAULT_BEACON_API_URL.to_string());
+    let url = format!("{base_url}/eth/v1/beacon/light_client/updates?start_period={period}&count=1");
+
+    let response: Vec<BeaconApiUpdateEnvelope> = ureq::get(&url)
+        .call()
+        .map_err(|e| crate::error::Error::beacon_api_fetch(url.clone(), e.to_string()))?
+        .into_json()
+        .map_err(|e| crate::error::Error::beacon_api_decode(e.to_string()))?;
+
+    let envelope = response
+        .into_iter()
+        .next()
+        .ok_or_else(|| crate::error::Error::beacon_api_decode(format!("no update for period {period}")))?;
+
+    envelope.data.try_into()
+}
+
+/// Beacon-chain light-client capability a route's source chain must support
+/// in order to relay sync-committee updates across a period boundary.
+/// Implemented for every `ChainHandle` via the shared REST fetch above, so
+/// routes whose source chain has no committee rotation (e.g. Axon) simply
+/// never call it.
+trait BeaconLightClientSource: ChainHandle {
+    fn fetch_light_client_update(&self, period: u64) -> Result<LightClientUpdate, crate::error::Error>;
+}
+
+impl<T: ChainHandle> BeaconLightClientSource for T {
+    fn fetch_light_client_update(&self, period: u64) -> Result<LightClientUpdate, crate::error::Error> {
+        fetch_beacon_light_client_update(period)
+    }
+}
+
+/// The headers pulled out of one `EventBatch`, ready to be packaged for the
+/// destination chain. `first_slot`/`last_slot` are `None` when the batch
+/// held no `NewBlock` events to relay, rather than falling back to a
+/// sentinel height.
+struct HeaderBatch {
+    first_slot: Option<Height>,
+    last_slot: Option<Height>,
+    count: u64,
+    target_height: u64,
+    msgs: Vec<Any>,
+}
+
+/// `first`/`last`/`count` of a sequence of relayed heights, with
+/// `first`/`last` as `None` rather than falling back to a sentinel height
+/// when there are none to summarize. Split out of `extract_headers` so the
+/// `Option<Height>` bookkeeping can be unit tested without a `ChainHandle`
+/// or a real `EventBatch`.
+struct HeightSummary {
+    first: Option<Height>,
+    last: Option<Height>,
+    count: u64,
+}
+
+fn summarize_heights(heights: impl Iterator<Item = Height>) -> HeightSummary {
+    let mut first = None;
+    let mut last = None;
+    let mut count = 0u64;
+    for height in heights {
+        first.get_or_insert(height);
+        last = Some(height);
+        count += 1;
+    }
+    HeightSummary { first, last, count }
+}
+
+/// One concrete header-relay route: a `(src, dst)` client-type pairing,
+/// together with the conversions needed to turn a source chain's
+/// `EventBatch` into messages the destination chain will accept.
+///
+/// Implementing this for a new `(ClientType, ClientType)` pairing is all a
+/// new chain combination needs to plug into `handle_event_batch`.
+trait HeaderRelayRoute<ChainA: ChainHandle, ChainB: ChainHandle> {
+    /// Client type expected on the source chain for this route.
+    fn src_client_type(&self) -> ClientType;
+    /// Client type expected on the destination chain for this route.
+    fn dst_client_type(&self) -> ClientType;
+    /// Tracking id this route tags its outbound messages with.
+    fn tracking_id(&self) -> NonCosmosTrackingId;
+
+    /// Pulls the relayable headers out of `event_batch`, building client
+    /// states against `src_chain`. Every route so far extracts headers the
+    /// same way (one client state per `NewBlock` event), so this has a
+    /// shared default; override it if a route's destination needs a
+    /// different message shape.
+    fn extract_headers(&self, src_chain: &ChainA, event_batch: &EventBatch) -> HeaderBatch {
+        let mut heights = Vec::new();
+        let msgs = event_batch
+            .events
+            .iter()
+            .filter_map(|event| {
+                if let IbcEvent::NewBlock(new_block) = event.event {
+                    let client_state = {
+                        let client_state = src_chain.build_client_state(
+                            new_block.height,
+                            crate::chain::client::ClientSettings::Other,
+                        );
+                        match client_state {
+                            Ok(value) => value,
+                            Err(err) => {
+                                error!("src_chain.build_client_state: {}", err);
+                                return None;
+                            }
+                        }
+                    };
+                    heights.push(new_block.height);
+                    return Some(client_state.into());
+                }
+                None
+            })
+            .collect();
+        let HeightSummary { first, last, count } = summarize_heights(heights.into_iter());
+        HeaderBatch {
+            first_slot: first,
+            last_slot: last,
+            count,
+            target_height: event_batch.height.revision_height(),
+            msgs,
+        }
+    }
+
+    /// Packages `msgs` as `TrackedMsgs` bound for this route's destination.
+    fn package(&self, msgs: Vec<Any>) -> TrackedMsgs {
+        TrackedMsgs {
+            msgs,
+            tracking_id: TrackingId::Static(self.tracking_id()),
+        }
+    }
+
+    /// The last height in `[start_height, target_height]` that a single
+    /// chase window may extend to without first needing a mid-window relay
+    /// step (e.g. a sync-committee update). Defaults to `target_height`,
+    /// i.e. no splitting.
+    fn window_end(&self, _start_height: u64, target_height: u64) -> u64 {
+        target_height
+    }
+
+    /// Relays whatever this route needs before headers can cross into
+    /// `period` can be verified by the destination chain. Routes whose
+    /// source chain has no committee rotation (e.g. Axon) have nothing to
+    /// do here.
+    fn relay_committee_update(
+        &self,
+        _src_chain: &ChainA,
+        _dst_chain: &ChainB,
+        _period: u64,
+    ) -> Result<(), crate::error::Error> {
+        Ok(())
+    }
+}
+
+/// Header relay from an ETH (Altair) light client to a CKB or Axon light
+/// client: both destinations verify ETH finality headers via the same
+/// sync-committee protocol, so they share this route implementation.
+struct EthRoute {
+    dst_client_type: ClientType,
+}
+
+impl<ChainA: ChainHandle, ChainB: ChainHandle> HeaderRelayRoute<ChainA, ChainB> for EthRoute {
+    fn src_client_type(&self) -> ClientType {
+        ClientType::Eth
+    }
+
+    fn dst_client_type(&self) -> ClientType {
+        self.dst_client_type
+    }
+
+    fn tracking_id(&self) -> NonCosmosTrackingId {
+        NonCosmosTrackingId::ETH_UPDATE_CLIENT
+    }
+
+    fn window_end(&self, start_height: u64, target_height: u64) -> u64 {
+        sync_committee_window_end(start_height, target_height)
+    }
+
+    fn relay_committee_update(
+        &self,
+        src_chain: &ChainA,
+        dst_chain: &ChainB,
+        period: u64,
+    ) -> Result<(), crate::error::Error> {
+        let update = src_chain.fetch_light_client_update(period)?;
+        info!(
+            "relaying sync committee update for period {} (attested slot {}, finalized slot {})",
+            period, update.attested_header.slot, update.finalized_header.slot
+        );
+        dst_chain.send_messages_and_wait_commit(self.package(vec![update.into()]))?;
+        Ok(())
+    }
+}
+
+/// Header relay from an Axon light client to a CKB light client. Axon does
+/// not rotate committees the way ETH does, so this route has nothing to do
+/// beyond the default `extract_headers`/`package` behavior.
+struct AxonToCkbRoute;
+
+impl<ChainA: ChainHandle, ChainB: ChainHandle> HeaderRelayRoute<ChainA, ChainB>
+    for AxonToCkbRoute
+{
+    fn src_client_type(&self) -> ClientType {
+        ClientType::Axon
+    }
+
+    fn dst_client_type(&self) -> ClientType {
+        ClientType::Ckb
+    }
+
+    fn tracking_id(&self) -> NonCosmosTrackingId {
+        NonCosmosTrackingId::AXON_UPDATE_CLIENT
+    }
+}
+
+/// Classifies a failed `send_messages_and_wait_commit` into the handful of
+/// shapes the chase loop actually needs to react to, replacing the previous
+/// nested matches against `ErrorDetail` internals. This is the single place
+/// that needs to know how destination-chain errors map to relay intent.
+#[derive(Debug)]
+enum RelayError {
+    /// The destination's on-chain (or native) tip is behind the headers we
+    /// tried to relay; chase from `onchain_tip` instead.
+    HeadersBehind { onchain_tip: u64 },
+    /// A transient failure worth retrying without changing course.
+    RetryableTransient,
+    /// The destination is missing a sync-committee update it needs before
+    /// it can verify any further headers; retrying the same send is
+    /// pointless until that update is relayed.
+    MissingSyncCommittee,
+    /// Anything else: not worth retrying.
+    Fatal,
+}
+
+/// The concrete light-client verification outcomes `classify` reacts to
+/// differently, extracted from the opaque `tendermint_light_client` error
+/// shape so the mapping to `RelayError` can be unit tested without
+/// constructing a real verifier error.
+enum LightClientFailure {
+    MissingLastBlockId { onchain_tip: u64 },
+    /// A header that fails signature verification is never going to pass
+    /// on retry; only a correct, differently-signed header would, and
+    /// resending the same one can't produce that.
+    InvalidSignature,
+    Other,
+}
+
+fn classify_light_client_failure(failure: LightClientFailure) -> RelayError {
+    match failure {
+        LightClientFailure::MissingLastBlockId { onchain_tip } => {
+            RelayError::HeadersBehind { onchain_tip }
+        }
+        LightClientFailure::InvalidSignature => RelayError::Fatal,
+        LightClientFailure::Other => RelayError::RetryableTransient,
+    }
+}
+
+fn classify(error: &crate::error::Error) -> RelayError {
+    match error.detail() {
+        LightClientVerification(e) => classify_light_client_failure(match &e.source {
+            MissingLastBlockId(height) => LightClientFailure::MissingLastBlockId {
+                onchain_tip: height.height.into(),
+            },
+            InvalidSignature { .. } => LightClientFailure::InvalidSignature,
+            _ => LightClientFailure::Other,
+        }),
+        // Anything else observed so far from `send_messages_and_wait_commit`
+        // (RPC timeouts, mempool/sequence errors, ...) has turned out to be
+        // transient in practice, so default to retrying rather than halting
+        // the whole chase on a single hiccup.
+        _ => RelayError::RetryableTransient,
+    }
+}
+
+/// Identifies a route for checkpointing purposes. Includes both chains'
+/// ids, not just their client types: two independently configured relay
+/// pairs sharing a client-type pairing (e.g. a second ETH->CKB deployment,
+/// or the same pairing against a different CKB chain-id) must not resolve
+/// to the same checkpoint file.
+fn route_key<ChainA: ChainHandle, ChainB: ChainHandle>(
+    route: &dyn HeaderRelayRoute<ChainA, ChainB>,
+    src_chain: &ChainA,
+    dst_chain: &ChainB,
+) -> String {
+    format!(
+        "{}-{}-{}-{}",
+        route.src_client_type().as_str(),
+        src_chain.id(),
+        route.dst_client_type().as_str(),
+        dst_chain.id()
+    )
+}
+
+fn checkpoint_path(route_key: &str) -> PathBuf {
+    let dir = std::env::var(CHECKPOINT_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_CHECKPOINT_DIR));
+    dir.join(format!("{route_key}.checkpoint"))
+}
+
+/// Loads the last `end_height` this route successfully relayed up to, if a
+/// checkpoint was persisted for it.
+fn load_checkpoint(route_key: &str) -> Option<u64> {
+    fs::read_to_string(checkpoint_path(route_key))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Persists `end_height` as the last height this route successfully relayed
+/// up to, so a restarted relay can resume from there instead of recomputing
+/// its start height from scratch.
+fn store_checkpoint(route_key: &str, end_height: u64) {
+    let path = checkpoint_path(route_key);
+    let dir = match path.parent() {
+        Some(dir) => dir,
+        None => return,
+    };
+    if let Err(err) = fs::create_dir_all(dir) {
+        error!("failed to create checkpoint directory {:?}: {}", dir, err);
+        return;
+    }
+    if let Err(err) = fs::write(&path, end_height.to_string()) {
+        error!("failed to persist checkpoint to {:?}: {}", path, err);
+    }
+}
+
+/// Sleep duration for the `retry_number`th retry: `min(base * 2^n, cap)`
+/// plus up to 20% jitter, so repeated failures back off instead of hammering
+/// the destination chain at a fixed cadence.
+fn backoff_sleep(retry_number: u8) -> Duration {
+    let exp = RETRY_BASE_SLEEP_INTERVAL
+        .checked_mul(1u32 << retry_number.min(16))
+        .unwrap_or(MAX_RETRY_SLEEP_INTERVAL);
+    let capped = std::cmp::min(exp, MAX_RETRY_SLEEP_INTERVAL);
+    let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..=capped.as_millis() as u64 / 5));
+    capped + jitter
+}
+
+/// Resolves the header-relay route for this `(src_chain, dst_chain)`
+/// pairing from their configured chain types, or an error if the pairing
+/// isn't one we know how to relay headers for.
+fn resolve_route<ChainA: ChainHandle, ChainB: ChainHandle>(
+    src_chain: &ChainA,
+    dst_chain: &ChainB,
+) -> Result<Box<dyn HeaderRelayRoute<ChainA, ChainB>>, crate::error::Error> {
+    let src_config = src_chain.config().unwrap();
+    let dst_config = dst_chain.config().unwrap();
+    match (&src_config, &dst_config) {
+        (ChainConfig::Eth(_), ChainConfig::Ckb(_)) => Ok(Box::new(EthRoute {
+            dst_client_type: ClientType::Ckb,
+        })),
+        (ChainConfig::Eth(_), ChainConfig::Axon(_)) => Ok(Box::new(EthRoute {
+            dst_client_type: ClientType::Axon,
+        })),
+        (ChainConfig::Axon(_), ChainConfig::Ckb(_)) => Ok(Box::new(AxonToCkbRoute)),
+        _ => Err(crate::error::Error::unsupported_header_relay_route(
+            format!("{:?}", src_config),
+            format!("{:?}", dst_config),
+        )),
+    }
+}
+
 pub fn handle_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
-    eth_chain: &ChainA,
-    ckb_chain: &ChainB,
+    src_chain: &ChainA,
+    dst_chain: &ChainB,
     event_batch: &EventBatch,
 ) {
-    let dst_chain = ckb_chain;
-    let src_chain = eth_chain;
-    if !matches!(src_chain.config().unwrap(), ChainConfig::Eth(_))
-        || !matches!(dst_chain.config().unwrap(), ChainConfig::Ckb(_))
-    {
-        error!("ignore header relay while src chain is not eth or dst chain is not ckb");
-        error!("src_chain: {:?}", src_chain);
-        error!("dst_chain: {:?}", dst_chain);
-        return;
-    }
+    let route = match resolve_route(src_chain, dst_chain) {
+        Ok(route) => route,
+        Err(err) => {
+            error!("no header relay route for this chain pairing: {}", err);
+            error!("src_chain: {:?}", src_chain);
+            error!("dst_chain: {:?}", dst_chain);
+            return;
+        }
+    };
 
     if event_batch.events.is_empty() {
         warn!("CAUTION: start to relay EMPTY headers");
@@ -36,72 +681,98 @@ pub fn handle_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
     }
 
     // assemble client states which are transformed from fianlity headers
-    let mut start_slot = 0;
-    let end_slot = event_batch.height.revision_height();
-    info!("start to relay headers up to {}", end_slot);
-    let any_client_states = event_batch
-        .events
-        .iter()
-        .filter_map(|event| {
-            if let IbcEvent::NewBlock(new_block) = event.event {
-                if start_slot == 0 {
-                    start_slot = new_block.height.revision_height();
-                }
-                let client_state = {
-                    let client_state = src_chain.build_client_state(
-                        new_block.height,
-                        crate::chain::client::ClientSettings::Other,
-                    );
-                    match client_state {
-                        Ok(value) => value,
-                        Err(err) => {
-                            error!("src_chain.build_client_state: {}", err);
-                            return None;
-                        }
-                    }
-                };
-                return Some(client_state.into());
-            }
-            None
-        })
-        .collect();
-
-    let tracked_msgs = TrackedMsgs {
-        msgs: any_client_states,
-        tracking_id: TrackingId::Static(NonCosmosTrackingId::ETH_UPDATE_CLIENT),
+    let header_batch = route.extract_headers(src_chain, event_batch);
+    let end_slot = header_batch.target_height;
+    let first_slot = match header_batch.first_slot {
+        Some(height) => height,
+        None => {
+            warn!("batch contained no NewBlock events to relay, nothing to do");
+            return;
+        }
     };
+    let start_slot = first_slot.revision_height();
+    info!(
+        "start to relay {} headers from slot {} to {} (batch height {})",
+        header_batch.count,
+        first_slot,
+        header_batch.last_slot.unwrap_or(first_slot),
+        end_slot
+    );
+
+    // a batch can only be relayed as-is if it does not straddle a
+    // sync-committee period boundary: headers signed by a new committee
+    // cannot be verified until that committee's `LightClientUpdate` has
+    // been relayed
+    let start_period = sync_committee_period(start_slot);
+    let end_period = sync_committee_period(end_slot);
+    if start_period != end_period {
+        warn!(
+            "batch crosses sync-committee period boundary ({} -> {}), fetching committee update before relaying",
+            start_period, end_period
+        );
+        for period in (start_period + 1)..=end_period {
+            if let Err(err) = route.relay_committee_update(src_chain, dst_chain, period) {
+                error!(
+                    "cannot obtain required sync committee update for period {}, halting relay: {}",
+                    period, err
+                );
+                return;
+            }
+        }
+    }
 
     // try sending header
-    let result = dst_chain.send_messages_and_wait_commit(tracked_msgs);
+    let result = dst_chain.send_messages_and_wait_commit(route.package(header_batch.msgs));
     if result.is_ok() {
         info!("finish relay headers from {} to {}", start_slot, end_slot);
+        store_checkpoint(&route_key(route.as_ref(), src_chain, dst_chain), end_slot);
         return;
     }
 
     // returned err indicates headers falling behind
     let error = result.unwrap_err();
-    let mut start_height = match error.detail() {
-        LightClientVerification(e) => match &e.source {
-            MissingLastBlockId(height) => {
-                warn!(
-                    "header {} is beyond onchain or native tip header {}, start to chase",
-                    start_slot, height.height
-                );
-                height.height.into()
-            }
-            _ => {
-                error!("receive unexpected error: {:?}", error);
-                return;
-            }
-        },
-        _ => {
-            error!("receive unexpected error: {:?}", error);
+    let start_height = match classify(&error) {
+        RelayError::HeadersBehind { onchain_tip } => {
+            warn!(
+                "header {} is beyond onchain or native tip header {}, start to chase",
+                start_slot, onchain_tip
+            );
+            onchain_tip
+        }
+        other => {
+            error!("receive unexpected error ({:?}): {}", other, error);
             return;
         }
     };
 
-    // start to chase lost headers
-    let target_height = end_slot;
+    chase_headers(route.as_ref(), src_chain, dst_chain, start_height, end_slot);
+}
+
+/// Chases lost headers from `start_height` up to `target_height`, never
+/// letting a single query/send window extend past `route.window_end`:
+/// when the next window would cross a boundary the route cares about, the
+/// route's `relay_committee_update` is run first.
+fn chase_headers<ChainA: ChainHandle, ChainB: ChainHandle>(
+    route: &dyn HeaderRelayRoute<ChainA, ChainB>,
+    src_chain: &ChainA,
+    dst_chain: &ChainB,
+    start_height: u64,
+    target_height: u64,
+) {
+    let route_key = route_key(route, src_chain, dst_chain);
+    let mut start_height = match load_checkpoint(&route_key) {
+        Some(checkpoint) if checkpoint + 1 > start_height => {
+            info!(
+                "resuming route {} from persisted checkpoint {} (tip-derived start was {})",
+                route_key,
+                checkpoint + 1,
+                start_height
+            );
+            checkpoint + 1
+        }
+        _ => start_height,
+    };
+
     let mut retry_number = 0;
     while start_height < target_height {
         if retry_number > 0 {
@@ -115,7 +786,9 @@ pub fn handle_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
                 start_height, target_height
             );
         }
-        let limit = std::cmp::min(MAX_HEADERS_IN_BATCH, target_height - start_height + 1);
+
+        let window_end = route.window_end(start_height, target_height);
+        let limit = std::cmp::min(MAX_HEADERS_IN_BATCH, window_end - start_height + 1);
         let request = QueryClientStatesRequest {
             pagination: Some(PageRequest {
                 offset: start_height,
@@ -145,22 +818,52 @@ pub fn handle_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
             "send chased headers from {} to {}",
             start_height, end_height
         );
-        match send_messages(dst_chain, client_states) {
+        match send_messages(route, dst_chain, client_states) {
             Ok(_) => {
                 info!(
-                    "headers from {} to {} are relayed to CKB",
+                    "headers from {} to {} are relayed",
                     start_height, end_height
                 );
                 retry_number = 0;
+                store_checkpoint(&route_key, end_height);
+
+                if end_height == window_end && end_height < target_height {
+                    let next_period = sync_committee_period(end_height + 1);
+                    if let Err(err) =
+                        route.relay_committee_update(src_chain, dst_chain, next_period)
+                    {
+                        error!(
+                            "{:?}: cannot obtain required sync committee update for period {}, halting relay: {}",
+                            RelayError::MissingSyncCommittee, next_period, err
+                        );
+                        return;
+                    }
+                }
+
                 start_height = end_height + 1;
             }
-            Err(e) => {
-                error!("encounter error and wait retry: {}", e);
-                retry_number += 1;
-                if let LightClientVerification(e) = e.detail() {
-                    if let MissingLastBlockId(_) = e.source {
-                        thread::sleep(MAX_RETRY_SLEEP_INTERVAL);
-                    }
+            Err(e) => match classify(&e) {
+                RelayError::HeadersBehind { onchain_tip } => {
+                    warn!(
+                        "destination tip is at {}, waiting before retry: {}",
+                        onchain_tip, e
+                    );
+                    retry_number += 1;
+                    thread::sleep(backoff_sleep(retry_number));
+                }
+                RelayError::RetryableTransient => {
+                    error!("encounter transient error, will retry: {}", e);
+                    retry_number += 1;
+                    thread::sleep(backoff_sleep(retry_number));
+                }
+                // `classify` never actually produces `MissingSyncCommittee` here:
+                // that case is caught proactively by `relay_committee_update`
+                // above, before a send that would hit it is even attempted.
+                // It's grouped with `Fatal` so this match stays exhaustive if
+                // a future destination-chain error ever does map to it.
+                RelayError::MissingSyncCommittee | RelayError::Fatal => {
+                    error!("encounter fatal error, halting relay: {}", e);
+                    return;
                 }
             }
         }
@@ -174,16 +877,139 @@ pub fn handle_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
     }
 }
 
-fn send_messages<Chain: ChainHandle>(
-    chain: &Chain,
+fn send_messages<ChainA: ChainHandle, ChainB: ChainHandle>(
+    route: &dyn HeaderRelayRoute<ChainA, ChainB>,
+    chain: &ChainB,
     client_states: Vec<IdentifiedAnyClientState>,
 ) -> Result<Vec<crate::event::IbcEventWithHeight>, crate::error::Error> {
-    let tracked_msgs = TrackedMsgs {
-        msgs: client_states
-            .into_iter()
-            .map(|s| s.client_state.into())
-            .collect(),
-        tracking_id: TrackingId::Static(NonCosmosTrackingId::ETH_UPDATE_CLIENT),
-    };
-    chain.send_messages_and_wait_commit(tracked_msgs)
-}
\ No newline at end of file
+    let msgs = client_states
+        .into_iter()
+        .map(|s| s.client_state.into())
+        .collect();
+    chain.send_messages_and_wait_commit(route.package(msgs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_committee_period_boundaries() {
+        assert_eq!(sync_committee_period(0), 0);
+        assert_eq!(sync_committee_period(SLOTS_PER_SYNC_COMMITTEE_PERIOD - 1), 0);
+        assert_eq!(sync_committee_period(SLOTS_PER_SYNC_COMMITTEE_PERIOD), 1);
+        assert_eq!(sync_committee_period(SLOTS_PER_SYNC_COMMITTEE_PERIOD * 3 + 7), 3);
+    }
+
+    #[test]
+    fn window_end_stays_within_target_when_target_is_closer() {
+        let target_height = 10;
+        assert_eq!(sync_committee_window_end(0, target_height), target_height);
+    }
+
+    #[test]
+    fn window_end_stops_at_period_boundary_when_target_is_further() {
+        let period_end = SLOTS_PER_SYNC_COMMITTEE_PERIOD - 1;
+        assert_eq!(
+            sync_committee_window_end(0, period_end + 1000),
+            period_end
+        );
+    }
+
+    #[test]
+    fn window_end_handles_start_height_mid_period() {
+        let start = SLOTS_PER_SYNC_COMMITTEE_PERIOD + 5;
+        let period_end = SLOTS_PER_SYNC_COMMITTEE_PERIOD * 2 - 1;
+        assert_eq!(sync_committee_window_end(start, period_end + 500), period_end);
+    }
+
+    #[test]
+    fn backoff_sleep_is_capped_at_max_retry_sleep_interval() {
+        // retry numbers past 16 are clamped to a 2^16 multiplier, so 200
+        // exercises that clamp as well as the min() cap.
+        for retry_number in [0, 1, 5, 16, 200] {
+            let sleep = backoff_sleep(retry_number);
+            assert!(
+                sleep <= MAX_RETRY_SLEEP_INTERVAL * 6 / 5,
+                "backoff_sleep({retry_number}) = {sleep:?} exceeds cap + jitter"
+            );
+            // from 5 retries on, base * 2^n (16s and up) is past the cap
+            if retry_number >= 5 {
+                assert!(
+                    sleep >= MAX_RETRY_SLEEP_INTERVAL,
+                    "backoff_sleep({retry_number}) = {sleep:?} is below the cap"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn backoff_sleep_grows_with_retry_number_below_the_cap() {
+        assert!(backoff_sleep(0) < backoff_sleep(1));
+        assert!(backoff_sleep(1) < backoff_sleep(2));
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_disk() {
+        let dir = std::env::temp_dir().join(format!(
+            "forcerelay-checkpoint-test-{}",
+            std::process::id()
+        ));
+        std::env::set_var(CHECKPOINT_DIR_ENV, &dir);
+
+        let route_key = "07-ethereum-07-ckb4eth";
+        assert_eq!(load_checkpoint(route_key), None);
+
+        store_checkpoint(route_key, 42);
+        assert_eq!(load_checkpoint(route_key), Some(42));
+
+        store_checkpoint(route_key, 100);
+        assert_eq!(load_checkpoint(route_key), Some(100));
+
+        std::env::remove_var(CHECKPOINT_DIR_ENV);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn classify_light_client_failure_maps_missing_last_block_id_to_headers_behind() {
+        match classify_light_client_failure(LightClientFailure::MissingLastBlockId {
+            onchain_tip: 7,
+        }) {
+            RelayError::HeadersBehind { onchain_tip } => assert_eq!(onchain_tip, 7),
+            other => panic!("expected HeadersBehind, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn classify_light_client_failure_maps_invalid_signature_to_fatal() {
+        match classify_light_client_failure(LightClientFailure::InvalidSignature) {
+            RelayError::Fatal => (),
+            other => panic!("expected Fatal, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn classify_light_client_failure_maps_other_to_retryable_transient() {
+        match classify_light_client_failure(LightClientFailure::Other) {
+            RelayError::RetryableTransient => (),
+            other => panic!("expected RetryableTransient, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn summarize_heights_of_empty_iterator_has_no_first_or_last() {
+        let summary = summarize_heights(std::iter::empty());
+        assert_eq!(summary.first, None);
+        assert_eq!(summary.last, None);
+        assert_eq!(summary.count, 0);
+    }
+
+    #[test]
+    fn summarize_heights_tracks_first_last_and_count() {
+        let heights = vec![Height::new(0, 10).unwrap(), Height::new(0, 20).unwrap()];
+        let summary = summarize_heights(heights.clone().into_iter());
+        assert_eq!(summary.first, Some(heights[0]));
+        assert_eq!(summary.last, Some(heights[1]));
+        assert_eq!(summary.count, 2);
+    }
+}